@@ -5,8 +5,8 @@ use axum::{
     response::{IntoResponse, IntoResponseParts, Response, ResponseParts},
     Extension,
 };
-use cookie_lib::PrivateJar;
-use std::{convert::Infallible, fmt, marker::PhantomData};
+use cookie_lib::{time::Duration, PrivateJar, SameSite};
+use std::{borrow::Cow, convert::Infallible, fmt, marker::PhantomData};
 
 /// Extractor that grabs private cookies from the request and manages the jar.
 ///
@@ -58,7 +58,8 @@ use std::{convert::Infallible, fmt, marker::PhantomData};
 /// ```
 pub struct PrivateCookieJar<K = Key> {
     jar: cookie_lib::CookieJar,
-    key: Key,
+    keys: KeyRing,
+    defaults: Option<CookieDefaults>,
     // The key used to extract the key extension. Allows users to use multiple keys for different
     // jars. Maybe a library wants its own key.
     _marker: PhantomData<K>,
@@ -68,7 +69,8 @@ impl<K> fmt::Debug for PrivateCookieJar<K> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("PrivateCookieJar")
             .field("jar", &self.jar)
-            .field("key", &"REDACTED")
+            .field("keys", &"REDACTED")
+            .field("defaults", &self.defaults)
             .finish()
     }
 }
@@ -77,29 +79,190 @@ impl<K> fmt::Debug for PrivateCookieJar<K> {
 impl<B, K> FromRequest<B> for PrivateCookieJar<K>
 where
     B: Send,
-    K: Into<Key> + Clone + Send + Sync + 'static,
+    K: Into<KeyRing> + Clone + Send + Sync + 'static,
 {
     type Rejection = <axum::Extension<K> as FromRequest<B>>::Rejection;
 
     async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
-        let key = Extension::<K>::from_request(req).await?.0.into();
+        let keys = Extension::<K>::from_request(req).await?.0.into();
 
         let mut jar = cookie_lib::CookieJar::new();
-        let mut private_jar = jar.private_mut(&key);
         for cookie in cookies_from_request(req) {
-            if let Some(cookie) = private_jar.decrypt(cookie) {
-                private_jar.add_original(cookie);
+            if let Some(plaintext) = jar.private_mut(keys.primary()).decrypt(cookie.clone()) {
+                jar.private_mut(keys.primary()).add_original(plaintext);
+                continue;
+            }
+
+            // The primary key couldn't authenticate this cookie. Fall back to the retired
+            // keys so cookies issued before a rotation keep decrypting, and silently
+            // re-wrap the plaintext under the primary key so it's re-encrypted the next
+            // time the jar is written back to the client.
+            let reencrypted = keys
+                .old_keys()
+                .iter()
+                .find_map(|key| jar.private_mut(key).decrypt(cookie.clone()));
+            if let Some(plaintext) = reencrypted {
+                jar.private_mut(keys.primary()).add(plaintext);
             }
         }
 
         Ok(Self {
             jar,
-            key,
+            keys,
+            defaults: None,
             _marker: PhantomData,
         })
     }
 }
 
+/// A ring of [`Key`]s that allows [`PrivateCookieJar`] to rotate its encryption key without
+/// instantly invalidating cookies that were issued under the previous one.
+///
+/// The [primary key](KeyRing::primary) is used to encrypt all outgoing cookies and is tried
+/// first when decrypting incoming ones. If that fails, each of the [old keys](KeyRing::old_keys)
+/// is tried in turn. A cookie that only decrypts under an old key is transparently re-encrypted
+/// under the primary key, and is migrated on the client once a handler returns the jar in its
+/// response.
+///
+/// A bare [`Key`] can be used anywhere a `KeyRing` is expected, and behaves like a ring with no
+/// retired keys.
+#[derive(Clone)]
+pub struct KeyRing {
+    primary: Key,
+    old: Vec<Key>,
+}
+
+impl fmt::Debug for KeyRing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyRing")
+            .field("primary", &"REDACTED")
+            .field("old", &"REDACTED")
+            .finish()
+    }
+}
+
+impl KeyRing {
+    /// Create a new `KeyRing` from just a primary key, with no retired keys to fall back to.
+    pub fn new(primary: Key) -> Self {
+        Self {
+            primary,
+            old: Vec::new(),
+        }
+    }
+
+    /// Create a new `KeyRing` with a primary key and a list of retired keys, tried in order,
+    /// that are only ever used to decrypt cookies issued before a rotation.
+    pub fn with_old_keys(primary: Key, old: Vec<Key>) -> Self {
+        Self { primary, old }
+    }
+
+    /// The key used to encrypt outgoing cookies and as the first key tried on decryption.
+    pub fn primary(&self) -> &Key {
+        &self.primary
+    }
+
+    /// The retired keys, tried in order after the primary key fails to decrypt a cookie.
+    pub fn old_keys(&self) -> &[Key] {
+        &self.old
+    }
+}
+
+impl From<Key> for KeyRing {
+    fn from(key: Key) -> Self {
+        Self::new(key)
+    }
+}
+
+/// Hardening attributes automatically stamped onto cookies passed to
+/// [`PrivateCookieJar::add`] via [`PrivateCookieJar::with_defaults`], unless the cookie already
+/// sets the attribute itself.
+///
+/// These mirror the safe-by-default flags frameworks like Rocket apply out of the box
+/// (`SameSite=Strict`, `Path=/`, `Secure`, `HttpOnly`), so applications don't have to repeat them
+/// at every call site.
+#[derive(Debug, Clone, Default)]
+pub struct CookieDefaults {
+    same_site: Option<SameSite>,
+    path: Option<Cow<'static, str>>,
+    secure: Option<bool>,
+    http_only: Option<bool>,
+    max_age: Option<Duration>,
+}
+
+impl CookieDefaults {
+    /// Create an empty set of defaults. Use the builder methods to configure attributes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Default `SameSite` attribute.
+    #[must_use]
+    pub fn same_site(mut self, value: SameSite) -> Self {
+        self.same_site = Some(value);
+        self
+    }
+
+    /// Default `Path` attribute.
+    #[must_use]
+    pub fn path(mut self, value: impl Into<Cow<'static, str>>) -> Self {
+        self.path = Some(value.into());
+        self
+    }
+
+    /// Default `Secure` attribute.
+    #[must_use]
+    pub fn secure(mut self, value: bool) -> Self {
+        self.secure = Some(value);
+        self
+    }
+
+    /// Default `HttpOnly` attribute.
+    #[must_use]
+    pub fn http_only(mut self, value: bool) -> Self {
+        self.http_only = Some(value);
+        self
+    }
+
+    /// Default `Max-Age` attribute.
+    #[must_use]
+    pub fn max_age(mut self, value: Duration) -> Self {
+        self.max_age = Some(value);
+        self
+    }
+
+    fn apply(&self, cookie: &mut Cookie<'static>) {
+        if cookie.same_site().is_none() {
+            if let Some(same_site) = self.same_site {
+                cookie.set_same_site(same_site);
+            }
+        }
+
+        if cookie.path().is_none() {
+            if let Some(path) = self.path.clone() {
+                cookie.set_path(path);
+            }
+        }
+
+        if cookie.secure().is_none() {
+            if let Some(secure) = self.secure {
+                cookie.set_secure(secure);
+            }
+        }
+
+        if cookie.http_only().is_none() {
+            if let Some(http_only) = self.http_only {
+                cookie.set_http_only(http_only);
+            }
+        }
+
+        if cookie.max_age().is_none() {
+            if let Some(max_age) = self.max_age {
+                cookie.set_max_age(max_age);
+            }
+        }
+    }
+}
+
 impl<K> PrivateCookieJar<K> {
     /// Get a cookie from the jar.
     ///
@@ -143,6 +306,10 @@ impl<K> PrivateCookieJar<K> {
     ///
     /// The value will automatically be percent-encoded.
     ///
+    /// If [`CookieDefaults`] have been configured with [`PrivateCookieJar::with_defaults`], any
+    /// attribute the cookie doesn't already set explicitly is filled in from them before it's
+    /// encrypted.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -155,17 +322,108 @@ impl<K> PrivateCookieJar<K> {
     /// ```
     #[must_use]
     #[allow(clippy::should_implement_trait)]
-    pub fn add(mut self, cookie: Cookie<'static>) -> Self {
+    pub fn add(mut self, mut cookie: Cookie<'static>) -> Self {
+        if let Some(defaults) = &self.defaults {
+            defaults.apply(&mut cookie);
+        }
         self.private_jar_mut().add(cookie);
         self
     }
 
+    /// Configure the [`CookieDefaults`] that are stamped onto every cookie passed to
+    /// [`PrivateCookieJar::add`] from now on, unless that cookie already sets the attribute
+    /// itself.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use axum_extra::extract::cookie::{CookieDefaults, PrivateCookieJar};
+    /// use cookie::SameSite;
+    ///
+    /// async fn handle(jar: PrivateCookieJar) -> PrivateCookieJar {
+    ///     jar.with_defaults(
+    ///         CookieDefaults::new()
+    ///             .same_site(SameSite::Strict)
+    ///             .http_only(true)
+    ///             .secure(true),
+    ///     )
+    /// }
+    /// ```
+    #[must_use]
+    pub fn with_defaults(mut self, defaults: CookieDefaults) -> Self {
+        self.defaults = Some(defaults);
+        self
+    }
+
     /// Authenticates and decrypts `cookie`, returning the plaintext version if decryption succeeds
     /// or `None` otherwise.
     pub fn decrypt(&self, cookie: Cookie<'static>) -> Option<Cookie<'static>> {
         self.private_jar().decrypt(cookie)
     }
 
+    /// Serialize `value` to JSON and add it to the jar as a private cookie named `name`.
+    ///
+    /// This lets the encrypted blob carry a typed payload (for example a small
+    /// `{ user_id, csrf, expires }` struct) instead of every caller hand-rolling
+    /// (de)serialization of structured secrets.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use axum_extra::extract::cookie::PrivateCookieJar;
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Session {
+    ///     user_id: u64,
+    /// }
+    ///
+    /// async fn handle(jar: PrivateCookieJar) -> PrivateCookieJar {
+    ///     jar.add_json("session", &Session { user_id: 1 }).unwrap()
+    /// }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn add_json<T>(
+        self,
+        name: impl Into<Cow<'static, str>>,
+        value: &T,
+    ) -> Result<Self, serde_json::Error>
+    where
+        T: serde::Serialize,
+    {
+        let json = serde_json::to_string(value)?;
+        Ok(self.add(Cookie::new(name, json)))
+    }
+
+    /// Get a cookie from the jar and deserialize its value as JSON.
+    ///
+    /// Returns `None` if no such cookie exists (or it can't be decrypted), and
+    /// `Some(Err(_))` if the cookie exists but its value isn't valid JSON for `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use axum_extra::extract::cookie::PrivateCookieJar;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Session {
+    ///     user_id: u64,
+    /// }
+    ///
+    /// async fn handle(jar: PrivateCookieJar) {
+    ///     let session: Option<Session> = jar.get_json("session").and_then(Result::ok);
+    /// }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn get_json<T>(&self, name: &str) -> Option<Result<T, serde_json::Error>>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.get(name)
+            .map(|cookie| serde_json::from_str(cookie.value()))
+    }
+
     /// Get an iterator over all cookies in the jar.
     ///
     /// Only cookies with valid authenticity and integrity are yielded by the iterator.
@@ -177,11 +435,19 @@ impl<K> PrivateCookieJar<K> {
     }
 
     fn private_jar(&self) -> PrivateJar<&'_ cookie_lib::CookieJar> {
-        self.jar.private(&self.key)
+        self.jar.private(self.keys.primary())
     }
 
     fn private_jar_mut(&mut self) -> PrivateJar<&'_ mut cookie_lib::CookieJar> {
-        self.jar.private_mut(&self.key)
+        self.jar.private_mut(self.keys.primary())
+    }
+
+    /// Get the raw, already-encrypted cookie as it would be sent to the client, so sibling
+    /// modules' tests (e.g. `Session`) can round-trip it back through a request without reaching
+    /// into this jar's private fields.
+    #[cfg(test)]
+    pub(crate) fn raw_cookie_for_test(&self, name: &str) -> Option<Cookie<'static>> {
+        self.jar.get(name).cloned()
     }
 }
 
@@ -218,3 +484,114 @@ impl<'a, K> Iterator for PrivateCookieJarIter<'a, K> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+
+    fn request(keys: &KeyRing, cookie_header: Option<&str>) -> RequestParts<Body> {
+        let mut builder = Request::builder();
+        if let Some(header) = cookie_header {
+            builder = builder.header(axum::http::header::COOKIE, header);
+        }
+        let mut req = builder.body(Body::empty()).unwrap();
+        req.extensions_mut().insert(keys.clone());
+        RequestParts::new(req)
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_retired_key_and_rewraps_under_primary() {
+        let old_key = Key::generate();
+        let primary_key = Key::generate();
+
+        // A cookie issued before the rotation, encrypted under the retired key only.
+        let mut issued_under_old = cookie_lib::CookieJar::new();
+        issued_under_old
+            .private_mut(&old_key)
+            .add(Cookie::new("secret", "hello"));
+        let wire_cookie = issued_under_old.get("secret").unwrap().clone();
+
+        let keys = KeyRing::with_old_keys(primary_key.clone(), vec![old_key]);
+        let cookie_header = format!("{}={}", wire_cookie.name(), wire_cookie.value());
+        let mut parts = request(&keys, Some(&cookie_header));
+
+        let jar = PrivateCookieJar::<KeyRing>::from_request(&mut parts)
+            .await
+            .unwrap();
+
+        assert_eq!(jar.get("secret").unwrap().value(), "hello");
+
+        // It should have been silently re-encrypted under the primary key, so it now decrypts
+        // with the primary key alone, without needing the retired key at all.
+        let rewrapped = jar.jar.private(&primary_key).get("secret");
+        assert_eq!(rewrapped.unwrap().value(), "hello");
+    }
+
+    #[tokio::test]
+    async fn drops_cookie_that_fails_under_every_key() {
+        let keys = KeyRing::with_old_keys(Key::generate(), vec![Key::generate()]);
+        let mut parts = request(&keys, Some("secret=not-a-valid-private-cookie"));
+
+        let jar = PrivateCookieJar::<KeyRing>::from_request(&mut parts)
+            .await
+            .unwrap();
+
+        assert!(jar.get("secret").is_none());
+    }
+
+    #[test]
+    fn cookie_defaults_only_fill_in_unset_attributes() {
+        let defaults = CookieDefaults::new()
+            .same_site(SameSite::Strict)
+            .secure(true)
+            .http_only(true);
+
+        let mut explicit = Cookie::new("a", "b");
+        explicit.set_secure(false);
+        defaults.apply(&mut explicit);
+        assert_eq!(explicit.secure(), Some(false));
+        assert_eq!(explicit.same_site(), Some(SameSite::Strict));
+        assert_eq!(explicit.http_only(), Some(true));
+
+        let mut bare = Cookie::new("c", "d");
+        defaults.apply(&mut bare);
+        assert_eq!(bare.secure(), Some(true));
+    }
+
+    #[cfg(feature = "serde")]
+    #[tokio::test]
+    async fn add_json_and_get_json_round_trip() {
+        #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+        struct Payload {
+            user_id: u64,
+        }
+
+        let keys = KeyRing::new(Key::generate());
+        let mut parts = request(&keys, None);
+        let jar = PrivateCookieJar::<KeyRing>::from_request(&mut parts)
+            .await
+            .unwrap();
+
+        let jar = jar
+            .add_json("session", &Payload { user_id: 42 })
+            .unwrap();
+
+        let round_tripped: Payload = jar.get_json("session").unwrap().unwrap();
+        assert_eq!(round_tripped, Payload { user_id: 42 });
+    }
+
+    #[cfg(feature = "serde")]
+    #[tokio::test]
+    async fn get_json_reports_deserialize_errors() {
+        let keys = KeyRing::new(Key::generate());
+        let mut parts = request(&keys, None);
+        let jar = PrivateCookieJar::<KeyRing>::from_request(&mut parts)
+            .await
+            .unwrap();
+
+        let jar = jar.add(Cookie::new("session", "not valid json"));
+
+        assert!(jar.get_json::<u64>("session").unwrap().is_err());
+    }
+}
@@ -0,0 +1,202 @@
+use super::{Cookie, CookieDefaults, Key, KeyRing, PrivateCookieJar, SignedCookieJar};
+use axum::{
+    async_trait,
+    extract::{FromRequest, RequestParts},
+    response::{IntoResponse, IntoResponseParts, Response, ResponseParts},
+};
+use std::{convert::Infallible, fmt};
+
+/// Extractor that manages both signed and private cookies in a single jar.
+///
+/// Use this when a handler needs some cookies to be merely tamper-proof (signed, readable by the
+/// client, see [`add_signed`](SignedAndPrivateCookieJar::add_signed)) and others fully
+/// confidential (encrypted, see [`add_private`](SignedAndPrivateCookieJar::add_private)), without
+/// extracting [`SignedCookieJar`] and [`PrivateCookieJar`] separately and returning both from the
+/// handler.
+///
+/// This is built directly on top of [`PrivateCookieJar`] and [`SignedCookieJar`], so it inherits
+/// their behavior, including [`KeyRing`] key rotation on the private side (when `K` converts into
+/// a [`KeyRing`] with retired keys; signed cookies are verified with the single [`Key`] that `K`
+/// converts into, and `KeyRing` itself does not convert into a `Key`) and, via
+/// [`with_private_defaults`](SignedAndPrivateCookieJar::with_private_defaults), [`CookieDefaults`]
+/// hardening on the private side.
+///
+/// Note that methods like [`SignedAndPrivateCookieJar::add_signed`] and
+/// [`SignedAndPrivateCookieJar::add_private`] update the jar and return it. This value _must_ be
+/// returned from the handler as part of the response for the changes to be propagated.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{Router, Extension, routing::get};
+/// use axum_extra::extract::cookie::{SignedAndPrivateCookieJar, Cookie, Key};
+///
+/// async fn handler(jar: SignedAndPrivateCookieJar) -> SignedAndPrivateCookieJar {
+///     jar.add_signed(Cookie::new("visible", "readable-but-tamper-proof"))
+///         .add_private(Cookie::new("secret", "fully-confidential"))
+/// }
+///
+/// let key = Key::generate();
+///
+/// let app = Router::new()
+///     .route("/", get(handler))
+///     .layer(Extension(key));
+/// # let app: Router<axum::body::Body> = app;
+/// ```
+pub struct SignedAndPrivateCookieJar<K = Key> {
+    private: PrivateCookieJar<K>,
+    signed: SignedCookieJar<K>,
+}
+
+impl<K> fmt::Debug for SignedAndPrivateCookieJar<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignedAndPrivateCookieJar")
+            .field("private", &self.private)
+            .field("signed", &self.signed)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl<B, K> FromRequest<B> for SignedAndPrivateCookieJar<K>
+where
+    B: Send,
+    K: Into<KeyRing> + Into<Key> + Clone + Send + Sync + 'static,
+{
+    type Rejection = <PrivateCookieJar<K> as FromRequest<B>>::Rejection;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let private = PrivateCookieJar::<K>::from_request(req).await?;
+        let signed = SignedCookieJar::<K>::from_request(req).await?;
+        Ok(Self { private, signed })
+    }
+}
+
+impl<K> SignedAndPrivateCookieJar<K> {
+    /// Get a private cookie from the jar.
+    ///
+    /// If the cookie exists and can be decrypted then it is returned in plaintext.
+    pub fn get_private(&self, name: &str) -> Option<Cookie<'static>> {
+        self.private.get(name)
+    }
+
+    /// Add a private (encrypted) cookie to the jar.
+    ///
+    /// The value will automatically be percent-encoded.
+    #[must_use]
+    pub fn add_private(mut self, cookie: Cookie<'static>) -> Self {
+        self.private = self.private.add(cookie);
+        self
+    }
+
+    /// Configure the [`CookieDefaults`] stamped onto every cookie passed to
+    /// [`SignedAndPrivateCookieJar::add_private`] from now on, unless that cookie already sets
+    /// the attribute itself. See [`PrivateCookieJar::with_defaults`].
+    #[must_use]
+    pub fn with_private_defaults(mut self, defaults: CookieDefaults) -> Self {
+        self.private = self.private.with_defaults(defaults);
+        self
+    }
+
+    /// Get a signed cookie from the jar.
+    ///
+    /// If the cookie exists and its signature is valid then it is returned, in plaintext, as it
+    /// was added.
+    pub fn get_signed(&self, name: &str) -> Option<Cookie<'static>> {
+        self.signed.get(name)
+    }
+
+    /// Add a signed cookie to the jar.
+    ///
+    /// The value is readable by the client but any tampering with it will be detected.
+    #[must_use]
+    pub fn add_signed(mut self, cookie: Cookie<'static>) -> Self {
+        self.signed = self.signed.add(cookie);
+        self
+    }
+
+    /// Remove a cookie from the jar, whether it was added as signed or private.
+    #[must_use]
+    pub fn remove(mut self, cookie: Cookie<'static>) -> Self {
+        self.private = self.private.remove(cookie.clone());
+        self.signed = self.signed.remove(cookie);
+        self
+    }
+}
+
+impl<K> IntoResponseParts for SignedAndPrivateCookieJar<K> {
+    type Error = Infallible;
+
+    fn into_response_parts(self, res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        let res = self.private.into_response_parts(res)?;
+        self.signed.into_response_parts(res)
+    }
+}
+
+impl<K> IntoResponse for SignedAndPrivateCookieJar<K> {
+    fn into_response(self) -> Response {
+        (self, ()).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+
+    fn request(key: &Key, cookie_header: Option<&str>) -> RequestParts<Body> {
+        let mut builder = Request::builder();
+        if let Some(header) = cookie_header {
+            builder = builder.header(axum::http::header::COOKIE, header);
+        }
+        let mut req = builder.body(Body::empty()).unwrap();
+        req.extensions_mut().insert(key.clone());
+        RequestParts::new(req)
+    }
+
+    fn wire_cookie_header(name: &str, value: &str) -> String {
+        format!("{name}={value}")
+    }
+
+    #[tokio::test]
+    async fn private_and_signed_cookies_land_in_their_own_jar() {
+        let key = Key::generate();
+
+        let mut seed = cookie_lib::CookieJar::new();
+        seed.private_mut(&key).add(Cookie::new("secret", "hidden"));
+        seed.signed_mut(&key).add(Cookie::new("visible", "tamper-evident"));
+
+        let private_wire = seed.get("secret").unwrap().clone();
+        let signed_wire = seed.get("visible").unwrap().clone();
+        let cookie_header = format!(
+            "{}; {}",
+            wire_cookie_header(private_wire.name(), private_wire.value()),
+            wire_cookie_header(signed_wire.name(), signed_wire.value()),
+        );
+
+        let mut parts = request(&key, Some(&cookie_header));
+        let jar = SignedAndPrivateCookieJar::<Key>::from_request(&mut parts)
+            .await
+            .unwrap();
+
+        assert_eq!(jar.get_private("secret").unwrap().value(), "hidden");
+        assert_eq!(jar.get_signed("visible").unwrap().value(), "tamper-evident");
+
+        // Each cookie only validates under the scheme it was actually added with.
+        assert!(jar.get_signed("secret").is_none());
+        assert!(jar.get_private("visible").is_none());
+    }
+
+    #[tokio::test]
+    async fn garbage_cookie_is_dropped_by_both_jars() {
+        let key = Key::generate();
+        let mut parts = request(&key, Some("mystery=not-signed-or-encrypted"));
+
+        let jar = SignedAndPrivateCookieJar::<Key>::from_request(&mut parts)
+            .await
+            .unwrap();
+
+        assert!(jar.get_private("mystery").is_none());
+        assert!(jar.get_signed("mystery").is_none());
+    }
+}
@@ -0,0 +1,288 @@
+//! A fully client-side, encrypted session built on top of [`PrivateCookieJar`].
+//!
+//! Unlike a server-backed session store, all session state lives in a single encrypted cookie,
+//! so there's nothing to provision or garbage-collect on the server. This mirrors the
+//! `secure-cookies` session feature found in frameworks like actix.
+
+// `Session` serializes its data through `PrivateCookieJar::add_json`/`get_json`, which are
+// themselves gated on "serde". Gate the whole module here too, rather than relying on callers
+// only reaching this module when "serde" happens to be enabled.
+#![cfg(feature = "serde")]
+
+use crate::extract::cookie::{CookieDefaults, Key, KeyRing, PrivateCookieJar};
+use axum::{
+    async_trait,
+    extract::{FromRequest, RequestParts},
+    response::{IntoResponse, IntoResponseParts, Response, ResponseParts},
+};
+use cookie_lib::SameSite;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Name of the private cookie the session is stored under.
+const SESSION_COOKIE_NAME: &str = "axum.session";
+
+/// The session cookie is the single most security-sensitive cookie this crate produces, so
+/// unlike a general-purpose [`PrivateCookieJar`] user, callers don't get a say: every session
+/// cookie is always stamped `SameSite=Strict`, `Path=/`, `Secure` and `HttpOnly`.
+fn session_cookie_defaults() -> CookieDefaults {
+    CookieDefaults::new()
+        .same_site(SameSite::Strict)
+        .path("/")
+        .secure(true)
+        .http_only(true)
+}
+
+/// Extractor and response type for a client-side, encrypted session.
+///
+/// `Session` reads a single private cookie (see [`SESSION_COOKIE_NAME`]), decrypts it, and
+/// deserializes it into an in-memory `HashMap<String, serde_json::Value>` that can be inspected
+/// and mutated with [`get`](Session::get), [`insert`](Session::insert),
+/// [`remove`](Session::remove) and [`clear`](Session::clear). The session is only re-serialized
+/// and written back to the client if it was actually mutated, except that a session cookie that
+/// only decrypted under a retired [`KeyRing`] key is sent back re-encrypted under the primary key
+/// even when unchanged.
+///
+/// If an [idle timeout or absolute expiry](Session::expire_in) was set and has since elapsed,
+/// the session is treated as empty, exactly as if no cookie had been sent at all.
+///
+/// # Example
+///
+/// ```rust
+/// use axum_extra::extract::Session;
+/// use axum::response::Redirect;
+///
+/// async fn handle(mut session: Session) -> (Session, Redirect) {
+///     let visits: u64 = session
+///         .get("visits")
+///         .and_then(|value| value.as_u64())
+///         .unwrap_or(0);
+///     session.insert("visits", (visits + 1).into());
+///     (session, Redirect::to("/"))
+/// }
+/// ```
+pub struct Session<K = Key> {
+    jar: PrivateCookieJar<K>,
+    data: HashMap<String, serde_json::Value>,
+    expires_at: Option<u64>,
+    dirty: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SessionPayload {
+    data: HashMap<String, serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires_at: Option<u64>,
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[async_trait]
+impl<B, K> FromRequest<B> for Session<K>
+where
+    B: Send,
+    K: Into<KeyRing> + Clone + Send + Sync + 'static,
+{
+    type Rejection = <PrivateCookieJar<K> as FromRequest<B>>::Rejection;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let jar = PrivateCookieJar::<K>::from_request(req)
+            .await?
+            .with_defaults(session_cookie_defaults());
+
+        let payload = jar
+            .get_json::<SessionPayload>(SESSION_COOKIE_NAME)
+            .and_then(Result::ok);
+
+        let (data, expires_at) = match payload {
+            // A cookie with an elapsed expiry is treated as if it were never sent.
+            Some(payload) if payload.expires_at.map_or(false, |at| at <= now_unix()) => {
+                (HashMap::new(), None)
+            }
+            Some(payload) => (payload.data, payload.expires_at),
+            None => (HashMap::new(), None),
+        };
+
+        Ok(Self {
+            jar,
+            data,
+            expires_at,
+            dirty: false,
+        })
+    }
+}
+
+impl<K> Session<K> {
+    /// Get a value previously stored in the session.
+    pub fn get(&self, key: &str) -> Option<&serde_json::Value> {
+        self.data.get(key)
+    }
+
+    /// Insert a value into the session, overwriting any previous value for `key`.
+    pub fn insert(&mut self, key: impl Into<String>, value: serde_json::Value) {
+        self.data.insert(key.into(), value);
+        self.dirty = true;
+    }
+
+    /// Remove a value from the session, returning it if it was present.
+    pub fn remove(&mut self, key: &str) -> Option<serde_json::Value> {
+        let value = self.data.remove(key);
+        if value.is_some() {
+            self.dirty = true;
+        }
+        value
+    }
+
+    /// Remove every value from the session.
+    pub fn clear(&mut self) {
+        if !self.data.is_empty() {
+            self.dirty = true;
+        }
+        self.data.clear();
+    }
+
+    /// Set the session to expire `duration` from now, whether that's used as an idle timeout
+    /// (call this again on every request to keep extending it) or an absolute expiry (set it
+    /// once and leave it).
+    ///
+    /// Once elapsed, the session cookie is still sent but its contents are treated as empty.
+    pub fn expire_in(&mut self, duration: Duration) {
+        self.expires_at = Some(now_unix() + duration.as_secs());
+        self.dirty = true;
+    }
+}
+
+impl<K> IntoResponseParts for Session<K> {
+    type Error = Infallible;
+
+    fn into_response_parts(self, res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        if !self.dirty {
+            return self.jar.into_response_parts(res);
+        }
+
+        let payload = SessionPayload {
+            data: self.data,
+            expires_at: self.expires_at,
+        };
+
+        // Serializing a `HashMap<String, serde_json::Value>` can't fail.
+        let jar = self
+            .jar
+            .add_json(SESSION_COOKIE_NAME, &payload)
+            .unwrap_or_else(|err| unreachable!("session payload failed to serialize: {err}"));
+
+        jar.into_response_parts(res)
+    }
+}
+
+impl<K> IntoResponse for Session<K> {
+    fn into_response(self) -> Response {
+        (self, ()).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+
+    fn request(keys: &KeyRing, cookie_header: Option<&str>) -> RequestParts<Body> {
+        let mut builder = Request::builder();
+        if let Some(header) = cookie_header {
+            builder = builder.header(axum::http::header::COOKIE, header);
+        }
+        let mut req = builder.body(Body::empty()).unwrap();
+        req.extensions_mut().insert(keys.clone());
+        RequestParts::new(req)
+    }
+
+    async fn empty_session(keys: &KeyRing) -> Session<KeyRing> {
+        let mut parts = request(keys, None);
+        Session::<KeyRing>::from_request(&mut parts).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn insert_remove_clear_track_the_dirty_flag() {
+        let keys = KeyRing::new(Key::generate());
+        let mut session = empty_session(&keys).await;
+
+        session.insert("a", 1.into());
+        assert!(session.dirty);
+
+        session.dirty = false;
+        assert_eq!(session.remove("missing"), None);
+        assert!(
+            !session.dirty,
+            "removing an absent key shouldn't dirty the session"
+        );
+
+        assert_eq!(session.remove("a"), Some(1.into()));
+        assert!(session.dirty);
+
+        session.dirty = false;
+        session.clear();
+        assert!(
+            !session.dirty,
+            "clearing an already-empty session shouldn't dirty it"
+        );
+    }
+
+    // Build the exact wire-format `Cookie` header a client would send back, from the
+    // already-encrypted cookie the jar holds for `name`.
+    fn cookie_header_for(jar: &PrivateCookieJar<KeyRing>, name: &str) -> String {
+        let encrypted = jar.raw_cookie_for_test(name).unwrap();
+        format!("{}={}", encrypted.name(), encrypted.value())
+    }
+
+    #[tokio::test]
+    async fn expired_session_is_treated_as_empty() {
+        let keys = KeyRing::new(Key::generate());
+        let mut parts = request(&keys, None);
+        let jar = PrivateCookieJar::<KeyRing>::from_request(&mut parts)
+            .await
+            .unwrap();
+
+        let expired = SessionPayload {
+            data: HashMap::from([("visits".to_owned(), 3.into())]),
+            expires_at: Some(now_unix() - 1),
+        };
+        let jar = jar.add_json(SESSION_COOKIE_NAME, &expired).unwrap();
+        let cookie_header = cookie_header_for(&jar, SESSION_COOKIE_NAME);
+
+        let mut parts = request(&keys, Some(&cookie_header));
+        let session = Session::<KeyRing>::from_request(&mut parts).await.unwrap();
+
+        assert!(session.get("visits").is_none());
+        assert!(session.expires_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn non_expired_session_round_trips_through_a_request() {
+        let keys = KeyRing::new(Key::generate());
+        let mut parts = request(&keys, None);
+        let jar = PrivateCookieJar::<KeyRing>::from_request(&mut parts)
+            .await
+            .unwrap();
+
+        let alive = SessionPayload {
+            data: HashMap::from([("visits".to_owned(), 3.into())]),
+            expires_at: Some(now_unix() + 3600),
+        };
+        let jar = jar.add_json(SESSION_COOKIE_NAME, &alive).unwrap();
+        let cookie_header = cookie_header_for(&jar, SESSION_COOKIE_NAME);
+
+        let mut parts = request(&keys, Some(&cookie_header));
+        let session = Session::<KeyRing>::from_request(&mut parts).await.unwrap();
+
+        assert_eq!(session.get("visits").and_then(|v| v.as_u64()), Some(3));
+    }
+}